@@ -2,7 +2,7 @@
 
 mod test;
 
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Vec, IntoVal, symbol_short, vec};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Address, Env, Vec, IntoVal, symbol_short, vec};
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 #[contracttype]
@@ -12,6 +12,61 @@ pub enum FundState {
     Closed,
 }
 
+/// Structured failures surfaced to callers instead of trapping the invocation.
+///
+/// Every mutating entrypoint returns `Result<_, FundError>` so a bad request
+/// unwinds cleanly with a reason rather than corrupting state mid-write.
+#[contracterror]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum FundError {
+    /// `performance_fee_percent` was 100 or greater.
+    FeeTooHigh = 1,
+    /// The fund has already been closed.
+    FundAlreadyClosed = 2,
+    /// The caller is not the stored fund manager.
+    NotManager = 3,
+    /// A required storage entry (manager, token) was never written. Unset
+    /// totals are read as zero and do not raise this error.
+    Uninitialized = 4,
+    /// A payout could not be priced because the fund has no outstanding shares.
+    DivideByZero = 5,
+    /// The requested amount is not positive, or exceeds the shares the caller holds.
+    InsufficientBalance = 6,
+    /// The fund is not in the state this operation requires.
+    InvalidState = 7,
+    /// A trading allocation change would push the total above 100%.
+    AllocationExceeded = 8,
+    /// The referenced trader has not been added to the fund.
+    UnknownTrader = 9,
+}
+
+/// A single investor's position in the fund.
+#[contracttype]
+pub struct InvestorPosition {
+    pub investor: Address,
+    pub deposit: i128,
+    pub shares: i128,
+}
+
+/// A single trader's performance-fee allocation, in percent.
+#[contracttype]
+pub struct TraderAllocation {
+    pub trader: Address,
+    pub percent: i128,
+}
+
+/// A one-call snapshot of the whole fund: its state, totals, and every
+/// investor and trader position.
+#[contracttype]
+pub struct FundSummary {
+    pub state: FundState,
+    pub total_deposited: i128,
+    pub total_shares: i128,
+    pub investors: Vec<InvestorPosition>,
+    pub traders: Vec<TraderAllocation>,
+}
+
 #[derive(Clone, PartialEq, Eq)]
 #[contracttype]
 pub enum DataKey {
@@ -24,8 +79,25 @@ pub enum DataKey {
     TotalDeposited,
     PerformanceFeePercent,
     Token,
+    Shares(Address),  // Shares held by an individual investor
+    TotalShares,      // Sum of all outstanding shares
+    // Per-investor high-water NAV per share, scaled by NAV_SCALE. This
+    // per-investor mark supersedes any fund-wide high-water mark: fees are
+    // charged against each investor's own entry price, not aggregate NAV.
+    HwmPerShare(Address),
+    TtlThreshold,     // Remaining-ledger threshold below which entries are extended
+    TtlExtendTo,      // Ledger count to extend live entries out to
 }
 
+/// Fallback TTL parameters used when a fund predates the configurable values
+/// or `bump_ttl` is called before `create` finishes writing them.
+const DEFAULT_TTL_THRESHOLD: u32 = 1_000;
+const DEFAULT_TTL_EXTEND_TO: u32 = 100_000;
+
+/// Fixed-point scale for per-share NAV, so a fractional share price survives
+/// integer storage when tracking each investor's high-water mark.
+const NAV_SCALE: i128 = 1_000_000_000;
+
 #[contract]
 pub struct AlphaFund;
 
@@ -38,11 +110,18 @@ impl AlphaFund {
     /// - `manager`: The address of the fund manager.
     /// - `performance_fee_percent`: The percentage of profits taken as a performance fee (must be less than 100).
     /// - `token`: The address of the token used for deposits and distributions.
+    /// - `ttl_threshold`: Remaining-ledger threshold below which entries are extended.
+    /// - `ttl_extend_to`: Ledger count to extend live entries out to.
     ///
-    /// # Panics
-    /// Panics if `performance_fee_percent` is 100 or greater.
-    pub fn create(env: Env, manager: Address, performance_fee_percent: i128, token: Address) {
-        assert!(performance_fee_percent < 100, "Performance fee must be less than 100");
+    /// # Errors
+    /// Returns [`FundError::FeeTooHigh`] if `performance_fee_percent` is 100 or greater.
+    pub fn create(env: Env, manager: Address, performance_fee_percent: i128, token: Address, ttl_threshold: u32, ttl_extend_to: u32) -> Result<(), FundError> {
+        if performance_fee_percent >= 100 {
+            return Err(FundError::FeeTooHigh);
+        }
+
+        env.storage().persistent().set(&DataKey::TtlThreshold, &ttl_threshold);
+        env.storage().persistent().set(&DataKey::TtlExtendTo, &ttl_extend_to);
 
         env.storage().persistent().set(&DataKey::FundState, &FundState::OpenToInvestors);
         env.storage().persistent().set(&DataKey::Manager, &manager);
@@ -57,30 +136,111 @@ impl AlphaFund {
         // Initialize the investors list
         let investors: Vec<Address> = vec![&env]; // Initialize an empty Vec for investors
         env.storage().persistent().set(&DataKey::Investors, &investors);
+
+        Self::bump_all(&env);
+
+        Ok(())
     }
 
-    /// Adds an investor to the fund and updates their deposit amount.
+    /// Deposits `deposit_amount` tokens from `investor` into the fund, minting
+    /// shares against the current NAV.
     ///
     /// # Parameters
     /// - `env`: The execution environment.
-    /// - `investor`: The address of the investor to be added.
+    /// - `investor`: The address depositing into the fund, who must authenticate.
     /// - `deposit_amount`: The amount the investor deposits into the fund.
     ///
     /// # Note
-    /// If the investor is already in the list, their deposit amount will be updated.
-    pub fn add_investor(env: &Env, investor: Address, deposit_amount: i128) {
+    /// The investor's tokens are actually moved into the contract via the
+    /// token's `transfer`, and `TotalDeposited` is incremented so the recorded
+    /// totals match the real balance. Shares are minted proportional to the NAV
+    /// at deposit time so that every investor owns the same value per share; the
+    /// first deposit mints shares one-for-one with the deposited amount.
+    ///
+    /// # Errors
+    /// - [`FundError::InvalidState`] if the fund is not in `FundState::OpenToInvestors`.
+    /// - [`FundError::InsufficientBalance`] if `deposit_amount` is not positive.
+    /// - [`FundError::Uninitialized`] if the token was never set.
+    pub fn deposit(env: Env, investor: Address, deposit_amount: i128) -> Result<(), FundError> {
+        investor.require_auth();
+
+        if Self::get_state(&env) != FundState::OpenToInvestors {
+            return Err(FundError::InvalidState);
+        }
+
+        // Reject a non-positive deposit with a typed error rather than letting
+        // it fall through to the token's `transfer`, which would trap.
+        if deposit_amount <= 0 {
+            return Err(FundError::InsufficientBalance);
+        }
+
         // Check if investor is already in the list
-        let mut investors: Vec<Address> = Self::get_investors(env);
+        let mut investors: Vec<Address> = Self::get_investors(&env);
         if !investors.contains(&investor) {
             investors.push_back(investor.clone()); // Add investor to the Vec
             env.storage().persistent().set(&DataKey::Investors, &investors);
         }
 
-        // Update the investor's deposit
+        // Mint shares against the NAV *before* this deposit lands. The first
+        // deposit into an empty fund mints one-for-one and sets the price.
+        let total_shares: i128 = env.storage().persistent()
+            .get(&DataKey::TotalShares)
+            .unwrap_or(0);
+        let shares_minted = if total_shares == 0 {
+            deposit_amount
+        } else {
+            let nav_before = Self::get_contract_balance(&env)?;
+            if nav_before == 0 {
+                deposit_amount
+            } else {
+                (deposit_amount * total_shares) / nav_before
+            }
+        };
+
+        // Pull the tokens in before recording them, so storage never claims a
+        // deposit the contract did not actually receive.
+        Self::receive_tokens(&env, &investor, deposit_amount)?;
+
+        let current_shares: i128 = env.storage().persistent()
+            .get(&DataKey::Shares(investor.clone()))
+            .unwrap_or(0);
+        env.storage().persistent().set(&DataKey::Shares(investor.clone()), &(current_shares + shares_minted));
+        env.storage().persistent().set(&DataKey::TotalShares, &(total_shares + shares_minted));
+
+        // Record this tranche's per-share entry price and fold it into the
+        // investor's high-water mark as a share-weighted average, so a later
+        // redemption charges the fee against what *this* investor paid rather
+        // than against aggregate fund appreciation they never earned.
+        if shares_minted > 0 {
+            let entry_price = (deposit_amount * NAV_SCALE) / shares_minted;
+            let prev_mark: i128 = env.storage().persistent()
+                .get(&DataKey::HwmPerShare(investor.clone()))
+                .unwrap_or(entry_price);
+            let blended = (current_shares * prev_mark + shares_minted * entry_price)
+                / (current_shares + shares_minted);
+            env.storage().persistent().set(&DataKey::HwmPerShare(investor.clone()), &blended);
+        }
+
+        // Update the investor's deposit and the fund-wide total together.
         let current_deposit = env.storage().persistent()
             .get(&DataKey::InvestorDeposit(investor.clone()))
             .unwrap_or(0);
         env.storage().persistent().set(&DataKey::InvestorDeposit(investor.clone()), &(current_deposit + deposit_amount));
+
+        let total_deposited: i128 = env.storage().persistent()
+            .get(&DataKey::TotalDeposited)
+            .unwrap_or(0);
+        let new_total = total_deposited + deposit_amount;
+        env.storage().persistent().set(&DataKey::TotalDeposited, &new_total);
+
+        env.events().publish(
+            (symbol_short!("deposit"),),
+            (investor.clone(), deposit_amount, new_total),
+        );
+
+        Self::bump_all(&env);
+
+        Ok(())
     }
 
     /// Closes the fund, distributing any remaining balance to investors and performance fees to traders.
@@ -89,54 +249,394 @@ impl AlphaFund {
     /// - `env`: The execution environment.
     /// - `manager`: The address of the fund manager, who must authenticate the action.
     ///
-    /// # Panics
-    /// Panics if the fund is already closed or if called by a non-manager address.
-    pub fn close_fund(env: Env, manager: Address) {
-        manager.require_auth();
-        let state: FundState = env.storage().persistent().get(&DataKey::FundState).unwrap_or(FundState::Closed);
-        assert_ne!(state, FundState::Closed, "Fund is already closed");
+    /// # Errors
+    /// - [`FundError::NotManager`] if called by an address other than the manager.
+    /// - [`FundError::FundAlreadyClosed`] if the fund is already closed.
+    /// - [`FundError::Uninitialized`] if the token was never set.
+    pub fn close_fund(env: Env, manager: Address) -> Result<(), FundError> {
+        Self::require_manager(&env, &manager)?;
+        if Self::get_state(&env) == FundState::Closed {
+            return Err(FundError::FundAlreadyClosed);
+        }
+
+        // Pay each investor their pro-rata share of the balance, crystallizing
+        // the performance fee owed on their own gain above their per-share
+        // high-water mark first. Charging the fee per position (rather than a
+        // single fund-wide figure) keeps close consistent with `redeem` and
+        // avoids socializing one investor's fee across the others; the shares
+        // math also avoids the rounding dust of truncated deposit percentages.
+        let contract_balance = Self::get_contract_balance(&env)?;
+        let total_shares: i128 = env.storage().persistent()
+            .get(&DataKey::TotalShares)
+            .unwrap_or(0);
+        if total_shares > 0 {
+            let price_per_share = (contract_balance * NAV_SCALE) / total_shares;
+            let investors: Vec<Address> = Self::get_investors(&env);
+            for investor in investors.iter() {
+                let shares = env.storage().persistent()
+                    .get(&DataKey::Shares(investor.clone()))
+                    .unwrap_or(0);
+                if shares > 0 {
+                    let deposit: i128 = env.storage().persistent()
+                        .get(&DataKey::InvestorDeposit(investor.clone()))
+                        .unwrap_or(0);
+                    let gross = (shares * contract_balance) / total_shares;
+                    let fee = Self::crystallize_fee(&env, &investor, shares, shares, deposit, price_per_share)?;
+                    let net = gross - fee;
+                    Self::transfer_tokens(&env, &investor, net)?;
+                    env.events().publish((symbol_short!("payout"),), (investor.clone(), net));
+                    // The position is paid out in full; burn it so it cannot
+                    // claim later deposits if the fund is reopened.
+                    env.storage().persistent().set(&DataKey::Shares(investor.clone()), &0i128);
+                    env.storage().persistent().set(&DataKey::InvestorDeposit(investor.clone()), &0i128);
+                }
+            }
+            env.storage().persistent().set(&DataKey::TotalShares, &0i128);
+            env.storage().persistent().set(&DataKey::TotalDeposited, &0i128);
+        }
+
+        let previous_state = Self::get_state(&env);
+        env.storage().persistent().set(&DataKey::FundState, &FundState::Closed);
+        env.events().publish((symbol_short!("state"),), (previous_state, FundState::Closed));
+
+        Self::bump_all(&env);
+
+        Ok(())
+    }
+
+    /// Redeems `shares` of an investor's position, paying out their pro-rata
+    /// share of the contract balance net of any owed performance fee.
+    ///
+    /// # Parameters
+    /// - `env`: The execution environment.
+    /// - `investor`: The redeeming investor, who must authenticate.
+    /// - `shares`: The number of shares to burn.
+    ///
+    /// # Note
+    /// Redemptions are only permitted while the fund is `OpenToInvestors`; they
+    /// are blocked during `Trading` so positions cannot be pulled mid-strategy.
+    /// A leaving investor still pays the performance fee on the gain embedded in
+    /// the redeemed portion, which is distributed to traders by allocation.
+    ///
+    /// # Errors
+    /// - [`FundError::InvalidState`] if the fund is not `OpenToInvestors`.
+    /// - [`FundError::InsufficientBalance`] if `shares` exceeds the investor's holding.
+    /// - [`FundError::DivideByZero`] if the fund has no outstanding shares to price against.
+    /// - [`FundError::Uninitialized`] if the token was never set.
+    pub fn redeem(env: Env, investor: Address, shares: i128) -> Result<(), FundError> {
+        investor.require_auth();
+
+        if Self::get_state(&env) != FundState::OpenToInvestors {
+            return Err(FundError::InvalidState);
+        }
+
+        let held: i128 = env.storage().persistent()
+            .get(&DataKey::Shares(investor.clone()))
+            .unwrap_or(0);
+        if shares <= 0 || shares > held {
+            return Err(FundError::InsufficientBalance);
+        }
+
+        // The payout divides by `total_shares`; guard the zero case explicitly
+        // so a fund with no outstanding shares reports a clean reason rather
+        // than trapping on the division.
+        let total_shares: i128 = env.storage().persistent()
+            .get(&DataKey::TotalShares)
+            .unwrap_or(0);
+        if total_shares <= 0 {
+            return Err(FundError::DivideByZero);
+        }
 
         let total_deposited: i128 = env.storage().persistent()
             .get(&DataKey::TotalDeposited)
             .unwrap_or(0);
 
-        // Check if there's profit
-        let contract_balance = Self::get_contract_balance(&env);
-        if contract_balance > total_deposited {
-            let profit = contract_balance - total_deposited;
-            let performance_fee_percent: i128 = env.storage().persistent()
-                .get(&DataKey::PerformanceFeePercent)
-                .unwrap_or(0);
-            let total_performance_fee = (profit * performance_fee_percent) / 100;
+        let contract_balance = Self::get_contract_balance(&env)?;
+        let gross_payout = (shares * contract_balance) / total_shares;
 
-            // Distribute performance fee to traders based on allocation
-            let traders: Vec<Address> = Self::get_traders(&env);
-            for trader in traders.iter() {
-                let alloc_percent = env.storage().persistent()
-                    .get(&DataKey::TradingAllocation(trader.clone()))
-                    .unwrap_or(0);
-                if alloc_percent > 0 {
-                    let trader_fee = (total_performance_fee * alloc_percent) / 100;
-                    Self::transfer_tokens(&env, &trader, trader_fee);
-                }
+        // Crystallize the performance fee owed on the gain embedded in the
+        // redeemed shares, measured against *this investor's* per-share
+        // high-water mark rather than a fund-wide mark, so a latecomer who
+        // bought in after the fund appreciated is not charged for gains that
+        // accrued before they joined.
+        let deposit: i128 = env.storage().persistent()
+            .get(&DataKey::InvestorDeposit(investor.clone()))
+            .unwrap_or(0);
+        let price_per_share = (contract_balance * NAV_SCALE) / total_shares;
+        let fee = Self::crystallize_fee(&env, &investor, shares, held, deposit, price_per_share)?;
+
+        // Pay the investor their net proceeds.
+        let net_payout = gross_payout - fee;
+        Self::transfer_tokens(&env, &investor, net_payout)?;
+        env.events().publish((symbol_short!("payout"),), (investor.clone(), net_payout));
+
+        // Burn the redeemed shares and retire the matching cost basis.
+        let deposit_reduction = (deposit * shares) / held;
+        env.storage().persistent().set(&DataKey::Shares(investor.clone()), &(held - shares));
+        env.storage().persistent().set(&DataKey::TotalShares, &(total_shares - shares));
+        env.storage().persistent().set(&DataKey::InvestorDeposit(investor.clone()), &(deposit - deposit_reduction));
+        env.storage().persistent().set(&DataKey::TotalDeposited, &(total_deposited - deposit_reduction));
+
+        Self::bump_all(&env);
+
+        Ok(())
+    }
+
+    /// Moves the fund from `OpenToInvestors` into `Trading`, opening the
+    /// allocation and trading entrypoints and freezing new deposits.
+    ///
+    /// # Parameters
+    /// - `env`: The execution environment.
+    /// - `manager`: The fund manager, who must authenticate the action.
+    ///
+    /// # Errors
+    /// - [`FundError::NotManager`] if called by a non-manager address.
+    /// - [`FundError::InvalidState`] if the fund is not currently `OpenToInvestors`.
+    pub fn start_trading(env: Env, manager: Address) -> Result<(), FundError> {
+        Self::require_manager(&env, &manager)?;
+        Self::transition(&env, FundState::OpenToInvestors, FundState::Trading)
+    }
+
+    /// Pauses trading, moving the fund from `Trading` back to
+    /// `OpenToInvestors` so deposits and redemptions resume.
+    ///
+    /// This is the only defined path out of `Trading` short of closing the
+    /// fund; a direct `Trading` -> `OpenToInvestors` edge is otherwise illegal.
+    ///
+    /// # Parameters
+    /// - `env`: The execution environment.
+    /// - `manager`: The fund manager, who must authenticate the action.
+    ///
+    /// # Errors
+    /// - [`FundError::NotManager`] if called by a non-manager address.
+    /// - [`FundError::InvalidState`] if the fund is not currently `Trading`.
+    pub fn stop_trading(env: Env, manager: Address) -> Result<(), FundError> {
+        Self::require_manager(&env, &manager)?;
+        Self::transition(&env, FundState::Trading, FundState::OpenToInvestors)
+    }
+
+    /// Reopens a `Closed` fund, moving it back to `OpenToInvestors`.
+    ///
+    /// This is the only defined edge out of `Closed`; every other entrypoint
+    /// treats `Closed` as terminal, so a fund can only be revived through this
+    /// explicit, manager-authorized path.
+    ///
+    /// # Parameters
+    /// - `env`: The execution environment.
+    /// - `manager`: The fund manager, who must authenticate the action.
+    ///
+    /// # Errors
+    /// - [`FundError::NotManager`] if called by a non-manager address.
+    /// - [`FundError::InvalidState`] if the fund is not currently `Closed`.
+    pub fn reopen(env: Env, manager: Address) -> Result<(), FundError> {
+        Self::require_manager(&env, &manager)?;
+        Self::transition(&env, FundState::Closed, FundState::OpenToInvestors)
+    }
+
+    /// Registers a new trader eligible for a performance-fee allocation.
+    ///
+    /// # Parameters
+    /// - `env`: The execution environment.
+    /// - `manager`: The fund manager, who must authenticate the action.
+    /// - `trader`: The address to add to the traders list (at 0% allocation).
+    ///
+    /// # Errors
+    /// - [`FundError::NotManager`] if called by a non-manager address.
+    /// - [`FundError::InvalidState`] if the fund is not currently `Trading`.
+    pub fn add_trader(env: Env, manager: Address, trader: Address) -> Result<(), FundError> {
+        Self::require_manager(&env, &manager)?;
+        if Self::get_state(&env) != FundState::Trading {
+            return Err(FundError::InvalidState);
+        }
+
+        let mut traders: Vec<Address> = Self::get_traders(&env);
+        if !traders.contains(&trader) {
+            traders.push_back(trader.clone());
+            env.storage().persistent().set(&DataKey::Traders, &traders);
+            env.storage().persistent().set(&DataKey::TradingAllocation(trader.clone()), &0i128);
+        }
+
+        Self::bump_all(&env);
+
+        Ok(())
+    }
+
+    /// Sets a trader's performance-fee allocation, keeping the sum of all
+    /// allocations at or below 100%.
+    ///
+    /// # Parameters
+    /// - `env`: The execution environment.
+    /// - `manager`: The fund manager, who must authenticate the action.
+    /// - `trader`: The trader whose allocation is being set.
+    /// - `percent`: The new allocation for `trader`, in percent.
+    ///
+    /// # Errors
+    /// - [`FundError::NotManager`] if called by a non-manager address.
+    /// - [`FundError::InvalidState`] if the fund is not currently `Trading` or
+    ///   `percent` is negative.
+    /// - [`FundError::UnknownTrader`] if the trader has not been added.
+    /// - [`FundError::AllocationExceeded`] if the new total would exceed 100%.
+    pub fn set_allocation(env: Env, manager: Address, trader: Address, percent: i128) -> Result<(), FundError> {
+        Self::require_manager(&env, &manager)?;
+        if Self::get_state(&env) != FundState::Trading {
+            return Err(FundError::InvalidState);
+        }
+        if percent < 0 {
+            return Err(FundError::InvalidState);
+        }
+
+        let traders: Vec<Address> = Self::get_traders(&env);
+        if !traders.contains(&trader) {
+            return Err(FundError::UnknownTrader);
+        }
+
+        // Recompute the total with this trader's current allocation swapped out
+        // for the new one, so the invariant holds regardless of order.
+        let mut total = 0i128;
+        for t in traders.iter() {
+            if t == trader {
+                continue;
             }
+            total += env.storage().persistent()
+                .get(&DataKey::TradingAllocation(t.clone()))
+                .unwrap_or(0);
         }
+        if total + percent > 100 {
+            return Err(FundError::AllocationExceeded);
+        }
+
+        env.storage().persistent().set(&DataKey::TradingAllocation(trader.clone()), &percent);
+
+        Self::bump_all(&env);
+
+        Ok(())
+    }
 
-        // Distribute remaining balance to investors based on deposits
-        let remaining_balance = Self::get_contract_balance(&env);
-        let investors: Vec<Address> = Self::get_investors(&env);
-        for investor in investors.iter() {
-            let deposit_amt = env.storage().persistent()
+    /// Returns a one-call snapshot of the fund: its state, totals, and every
+    /// investor and trader position.
+    ///
+    /// The summary walks the canonical `Investors` and `Traders` registries so
+    /// it stays in sync with whatever keys those lists reference, rather than
+    /// hand-maintaining a parallel list of addresses to read.
+    pub fn fund_summary(env: Env) -> FundSummary {
+        let mut investors: Vec<InvestorPosition> = vec![&env];
+        for investor in Self::get_investors(&env).iter() {
+            let deposit = env.storage().persistent()
                 .get(&DataKey::InvestorDeposit(investor.clone()))
                 .unwrap_or(0);
-            if deposit_amt > 0 {
-                let percentage = (deposit_amt * 100) / total_deposited;
-                let fraction_to_pay = (remaining_balance * percentage) / 100;
-                Self::transfer_tokens(&env, &investor, fraction_to_pay);
+            let shares = env.storage().persistent()
+                .get(&DataKey::Shares(investor.clone()))
+                .unwrap_or(0);
+            investors.push_back(InvestorPosition { investor: investor.clone(), deposit, shares });
+        }
+
+        let mut traders: Vec<TraderAllocation> = vec![&env];
+        for trader in Self::get_traders(&env).iter() {
+            let percent = env.storage().persistent()
+                .get(&DataKey::TradingAllocation(trader.clone()))
+                .unwrap_or(0);
+            traders.push_back(TraderAllocation { trader: trader.clone(), percent });
+        }
+
+        FundSummary {
+            state: Self::get_state(&env),
+            total_deposited: env.storage().persistent().get(&DataKey::TotalDeposited).unwrap_or(0),
+            total_shares: env.storage().persistent().get(&DataKey::TotalShares).unwrap_or(0),
+            investors,
+            traders,
+        }
+    }
+
+    /// Crystallizes the performance fee owed on `shares` of `investor`'s
+    /// position, valued at `price_per_share` and measured against the
+    /// investor's own per-share high-water mark (defaulting to their cost
+    /// basis). The fee is distributed to traders by allocation and the return
+    /// value is the total paid to traders so the caller can net it out of the
+    /// payout; any part of the fee not covered by an allocation stays with the
+    /// investor.
+    ///
+    /// The per-share mark is *not* advanced: it is a property of every share in
+    /// the position, so remarking it on a partial redemption would let the
+    /// gain embedded in the unredeemed shares escape the fee. Each share keeps
+    /// its entry mark until it is itself redeemed or the fund closes, so its
+    /// gain is charged exactly once and never twice. This per-investor mark
+    /// takes the place of a fund-wide high-water mark.
+    fn crystallize_fee(
+        env: &Env,
+        investor: &Address,
+        shares: i128,
+        held: i128,
+        deposit: i128,
+        price_per_share: i128,
+    ) -> Result<i128, FundError> {
+        let mark: i128 = env.storage().persistent()
+            .get(&DataKey::HwmPerShare(investor.clone()))
+            .unwrap_or(if held > 0 { (deposit * NAV_SCALE) / held } else { price_per_share });
+        if price_per_share <= mark {
+            return Ok(0);
+        }
+
+        let gain_per_share = price_per_share - mark;
+        let redeemed_gain = (gain_per_share * shares) / NAV_SCALE;
+        let performance_fee_percent: i128 = env.storage().persistent()
+            .get(&DataKey::PerformanceFeePercent)
+            .unwrap_or(0);
+        let fee = (redeemed_gain * performance_fee_percent) / 100;
+
+        let mut paid = 0i128;
+        let traders: Vec<Address> = Self::get_traders(env);
+        for trader in traders.iter() {
+            let alloc_percent = env.storage().persistent()
+                .get(&DataKey::TradingAllocation(trader.clone()))
+                .unwrap_or(0);
+            if alloc_percent > 0 {
+                let trader_fee = (fee * alloc_percent) / 100;
+                Self::transfer_tokens(env, &trader, trader_fee)?;
+                env.events().publish((symbol_short!("fee_paid"),), (trader.clone(), trader_fee));
+                paid += trader_fee;
             }
         }
 
-        env.storage().persistent().set(&DataKey::FundState, &FundState::Closed);
+        Ok(paid)
+    }
+
+    /// Checks that `manager` matches the stored manager and requires its
+    /// authorization.
+    ///
+    /// # Errors
+    /// - [`FundError::Uninitialized`] if no manager has been set.
+    /// - [`FundError::NotManager`] if `manager` is not the stored fund manager.
+    fn require_manager(env: &Env, manager: &Address) -> Result<(), FundError> {
+        let stored: Address = env.storage().persistent()
+            .get(&DataKey::Manager)
+            .ok_or(FundError::Uninitialized)?;
+        if &stored != manager {
+            return Err(FundError::NotManager);
+        }
+        manager.require_auth();
+        Ok(())
+    }
+
+    /// Reads the current fund state, defaulting to `Closed` when unset so an
+    /// uninitialized fund refuses mutating operations.
+    fn get_state(env: &Env) -> FundState {
+        env.storage().persistent().get(&DataKey::FundState).unwrap_or(FundState::Closed)
+    }
+
+    /// Performs a guarded state transition, requiring the current state to
+    /// equal `from` before writing `to`.
+    ///
+    /// # Errors
+    /// Returns [`FundError::InvalidState`] if the current state is not `from`,
+    /// rejecting illegal edges.
+    fn transition(env: &Env, from: FundState, to: FundState) -> Result<(), FundError> {
+        if Self::get_state(env) != from {
+            return Err(FundError::InvalidState);
+        }
+        env.storage().persistent().set(&DataKey::FundState, &to);
+        env.events().publish((symbol_short!("state"),), (from, to));
+        Self::bump_all(env);
+        Ok(())
     }
 
     /// Retrieves the current balance of the contract.
@@ -146,15 +646,20 @@ impl AlphaFund {
     ///
     /// # Returns
     /// The current balance of the contract as an `i128`.
-    fn get_contract_balance(env: &Env) -> i128 {
-        let token: Address = env.storage().persistent().get(&DataKey::Token).unwrap();
+    ///
+    /// # Errors
+    /// Returns [`FundError::Uninitialized`] if the token address was never set.
+    fn get_contract_balance(env: &Env) -> Result<i128, FundError> {
+        let token: Address = env.storage().persistent()
+            .get(&DataKey::Token)
+            .ok_or(FundError::Uninitialized)?;
         let contract_id = env.current_contract_address();
 
         // Explicitly specify the expected return type when calling invoke_contract
         let balance: i128 = env
             .invoke_contract::<i128>(&token, &symbol_short!("balance"), (contract_id,).into_val(env));
 
-        balance
+        Ok(balance)
     }
 
     /// Transfers tokens from the contract to a specified recipient.
@@ -163,14 +668,42 @@ impl AlphaFund {
     /// - `env`: The execution environment.
     /// - `recipient`: The address of the recipient.
     /// - `amount`: The amount of tokens to transfer.
-    fn transfer_tokens(env: &Env, recipient: &Address, amount: i128) {
-        let token: Address = env.storage().persistent().get(&DataKey::Token).unwrap();
+    ///
+    /// # Errors
+    /// Returns [`FundError::Uninitialized`] if the token address was never set.
+    fn transfer_tokens(env: &Env, recipient: &Address, amount: i128) -> Result<(), FundError> {
+        let token: Address = env.storage().persistent()
+            .get(&DataKey::Token)
+            .ok_or(FundError::Uninitialized)?;
         let contract_id = env.current_contract_address();
         env.invoke_contract::<()>(
             &token,
             &symbol_short!("transfer"),
             (contract_id, recipient, amount).into_val(env)
         );
+        Ok(())
+    }
+
+    /// Pulls tokens from `from` into the contract via the token's `transfer`.
+    ///
+    /// # Parameters
+    /// - `env`: The execution environment.
+    /// - `from`: The address the tokens are drawn from.
+    /// - `amount`: The amount of tokens to pull in.
+    ///
+    /// # Errors
+    /// Returns [`FundError::Uninitialized`] if the token address was never set.
+    fn receive_tokens(env: &Env, from: &Address, amount: i128) -> Result<(), FundError> {
+        let token: Address = env.storage().persistent()
+            .get(&DataKey::Token)
+            .ok_or(FundError::Uninitialized)?;
+        let contract_id = env.current_contract_address();
+        env.invoke_contract::<()>(
+            &token,
+            &symbol_short!("transfer"),
+            (from, contract_id, amount).into_val(env)
+        );
+        Ok(())
     }
 
     /// Retrieves the list of traders from the contract's storage.
@@ -195,5 +728,63 @@ impl AlphaFund {
         env.storage().persistent().get(&DataKey::Investors).unwrap_or(vec![&env])
     }
 
+    /// Refreshes the time-to-live of every stored entry (fund state, token,
+    /// totals, and each investor/trader key) plus the instance, so a fund that
+    /// sits idle for long stretches is not archived out from under callers.
+    ///
+    /// This is a permissionless maintenance entrypoint: anyone may pay to keep
+    /// the fund resident.
+    pub fn bump_ttl(env: Env) {
+        Self::bump_all(&env);
+    }
+
+    /// Reads the configured TTL threshold / extend-to, falling back to the
+    /// module defaults when the fund predates them.
+    fn ttl_params(env: &Env) -> (u32, u32) {
+        let threshold = env.storage().persistent().get(&DataKey::TtlThreshold).unwrap_or(DEFAULT_TTL_THRESHOLD);
+        let extend_to = env.storage().persistent().get(&DataKey::TtlExtendTo).unwrap_or(DEFAULT_TTL_EXTEND_TO);
+        (threshold, extend_to)
+    }
+
+    /// Extends a single persistent entry's TTL, skipping keys that were never
+    /// written so the call never traps on a missing entry.
+    fn bump_key(env: &Env, key: &DataKey, threshold: u32, extend_to: u32) {
+        if env.storage().persistent().has(key) {
+            env.storage().persistent().extend_ttl(key, threshold, extend_to);
+        }
+    }
+
+    /// Extends the instance and the full set of persistent keys the fund
+    /// depends on. Called from every mutating entrypoint and from `bump_ttl`.
+    fn bump_all(env: &Env) {
+        let (threshold, extend_to) = Self::ttl_params(env);
+        env.storage().instance().extend_ttl(threshold, extend_to);
+
+        let keys = [
+            DataKey::FundState,
+            DataKey::Manager,
+            DataKey::Traders,
+            DataKey::Investors,
+            DataKey::TotalDeposited,
+            DataKey::PerformanceFeePercent,
+            DataKey::Token,
+            DataKey::TotalShares,
+            DataKey::TtlThreshold,
+            DataKey::TtlExtendTo,
+        ];
+        for key in keys.iter() {
+            Self::bump_key(env, key, threshold, extend_to);
+        }
+
+        for investor in Self::get_investors(env).iter() {
+            Self::bump_key(env, &DataKey::InvestorDeposit(investor.clone()), threshold, extend_to);
+            Self::bump_key(env, &DataKey::Shares(investor.clone()), threshold, extend_to);
+            Self::bump_key(env, &DataKey::HwmPerShare(investor.clone()), threshold, extend_to);
+        }
+        for trader in Self::get_traders(env).iter() {
+            Self::bump_key(env, &DataKey::TradingAllocation(trader.clone()), threshold, extend_to);
+        }
+    }
+
 
 }